@@ -1,21 +1,92 @@
 use anyhow::Result;
-use minigem::{Line, LineKind, Lines, Request};
+use minigem::{Line, LineKind, Lines};
 
+use crate::network::{load_page, NetworkEvent};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
-use std::thread;
+use tui::layout::Rect;
 use url::{ParseError, Url};
 
 pub enum Action {
     PageRequest { link: String, push_history: bool },
 }
 
-enum NetworkEvent {
-    PageLoaded {
-        address: String,
-        lines: Vec<Line>,
-        push_history: bool,
-    },
+/// Which half of the `m` / `` ` `` mark workflow the next character key
+/// completes.
+enum PendingMark {
+    Set,
+    Jump,
+}
+
+const BOOKMARKS_ADDRESS: &str = "comet://bookmarks";
+
+fn bookmarks_path() -> Option<PathBuf> {
+    let home = std::env::var("HOME").ok()?;
+    Some(PathBuf::from(home).join(".config/comet/bookmarks"))
+}
+
+fn load_bookmarks() -> Vec<String> {
+    bookmarks_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|contents| {
+            contents
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn save_bookmarks(bookmarks: &[String]) {
+    let path = match bookmarks_path() {
+        Some(path) => path,
+        None => return,
+    };
+    if let Some(parent) = path.parent() {
+        if std::fs::create_dir_all(parent).is_err() {
+            return;
+        }
+    }
+    let _ = std::fs::write(path, bookmarks.join("\n") + "\n");
+}
+
+/// Finds all case-insensitive occurrences of `query` in `text`, returning
+/// each as `(byte_start, byte_len)` measured against `text` itself so the
+/// result always slices `text` at valid char boundaries.
+fn find_matches(text: &str, query: &str) -> Vec<(usize, usize)> {
+    let query_chars: Vec<char> = query.chars().collect();
+    if query_chars.is_empty() {
+        return vec![];
+    }
+
+    let text_chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut matches = Vec::new();
+
+    for start in 0..text_chars.len() {
+        if start + query_chars.len() > text_chars.len() {
+            break;
+        }
+        let is_match = query_chars.iter().enumerate().all(|(i, query_char)| {
+            text_chars[start + i]
+                .1
+                .to_lowercase()
+                .eq(query_char.to_lowercase())
+        });
+        if !is_match {
+            continue;
+        }
+
+        let byte_start = text_chars[start].0;
+        let byte_end = text_chars
+            .get(start + query_chars.len())
+            .map(|(i, _)| *i)
+            .unwrap_or(text.len());
+        matches.push((byte_start, byte_end - byte_start));
+    }
+
+    matches
 }
 
 pub struct Page {
@@ -23,6 +94,7 @@ pub struct Page {
     pub link_indices: Vec<usize>,
     pub link_numbers: HashMap<usize, usize>,
     pub highlighted_link: Option<usize>,
+    pub heading_indices: Vec<usize>,
 }
 
 pub struct History {
@@ -74,38 +146,42 @@ pub struct App {
     pub page: Page,
     pub scroll: u16,
     pub height: u16,
+    /// The screen area the page is rendered into, kept in sync by
+    /// `render_app` so mouse clicks can be translated into page lines.
+    pub page_area: Rect,
     pub search: String,
     pub address: String,
     pub history: History,
+    /// Prompt text from the last `1x` INPUT response, if we're waiting on
+    /// the user to answer one.
+    pub input_prompt: Option<String>,
+    pub input_buffer: String,
+    pending_input_address: Option<String>,
+    pending_input_push_history: bool,
+    /// In-page search, triggered by `/`. Distinct from `search`, which is
+    /// the URL bar.
+    pub searching: bool,
+    pub search_query: String,
+    pub matches: Vec<(usize, usize, usize)>,
+    pub current_match: Option<usize>,
+    /// Single-key marks set with `m`<letter> and restored with `` ` ``<letter>.
+    marks: HashMap<char, (String, u16)>,
+    pending_mark: Option<PendingMark>,
+    /// Scroll to restore once the in-flight page load (a mark jump) lands.
+    pending_scroll: Option<u16>,
+    pub bookmarks: Vec<String>,
+    /// Status code and MIME meta from the last response, for the footer.
+    /// Any `6x` client-certificate response is recorded as status 60.
+    pub last_status: Option<u8>,
+    pub last_meta: Option<String>,
+    /// Table-of-contents overlay, toggled with `t`. `toc_selected` indexes
+    /// into `page.heading_indices`, mirroring `highlighted_link`.
+    pub toc_open: bool,
+    pub toc_selected: Option<usize>,
     in_rx: Receiver<NetworkEvent>,
     _in_tx: Sender<NetworkEvent>,
 }
 
-fn load_page(url: String, push_history: bool, cb: Sender<NetworkEvent>) {
-    thread::spawn(move || {
-        eprintln!("sending request");
-        let res = Request::new(&url).send().unwrap();
-        eprintln!(
-            "got response with status: {:?}, meta: {:?}",
-            res.status, res.meta
-        );
-        let lines = Lines::from(res.body);
-        let mut buf = Vec::new();
-
-        for line in lines {
-            let line = line.unwrap();
-            buf.push(line);
-        }
-
-        cb.send(NetworkEvent::PageLoaded {
-            address: url,
-            push_history,
-            lines: buf,
-        })
-        .unwrap();
-    });
-}
-
 #[allow(clippy::new_without_default)]
 impl App {
     pub fn new() -> Self {
@@ -117,12 +193,30 @@ impl App {
                 link_indices: vec![],
                 link_numbers: HashMap::new(),
                 highlighted_link: None,
+                heading_indices: vec![],
             },
             search: "gemini://gemini.circumlunar.space/".to_string(),
             address: "gemini://gemini.circumlunar.space/".to_string(),
             height: 0,
+            page_area: Rect::default(),
             scroll: 0,
             history: History::new(),
+            input_prompt: None,
+            input_buffer: String::new(),
+            pending_input_address: None,
+            pending_input_push_history: false,
+            searching: false,
+            search_query: String::new(),
+            matches: Vec::new(),
+            current_match: None,
+            marks: HashMap::new(),
+            pending_mark: None,
+            pending_scroll: None,
+            bookmarks: load_bookmarks(),
+            last_status: None,
+            last_meta: None,
+            toc_open: false,
+            toc_selected: None,
             in_rx: rx,
             _in_tx: tx,
         }
@@ -241,6 +335,285 @@ impl App {
         }
     }
 
+    /// Translates a mouse click at the given screen column/row into a page
+    /// line, accounting for `scroll` and the page block's border, and
+    /// follows the link there if one exists.
+    pub fn follow_link_at(&mut self, column: u16, row: u16) {
+        let area = self.page_area;
+        let inner_x = area.x + 1;
+        let inner_y = area.y + 1;
+        let inner_right = area.x + area.width.saturating_sub(1);
+        let inner_bottom = area.y + area.height.saturating_sub(1);
+
+        if column < inner_x || column >= inner_right || row < inner_y || row >= inner_bottom {
+            return;
+        }
+
+        let line_index = self.scroll as usize + (row - inner_y) as usize;
+        if let Some(&link_index) = self.page.link_numbers.get(&line_index) {
+            self.page.highlighted_link = Some(link_index);
+            self.request_page_from_selected();
+        }
+    }
+
+    pub fn input_push_char(&mut self, c: char) {
+        if self.input_prompt.is_some() {
+            self.input_buffer.push(c);
+        }
+    }
+
+    pub fn input_backspace(&mut self) {
+        if self.input_prompt.is_some() {
+            self.input_buffer.pop();
+        }
+    }
+
+    pub fn input_cancel(&mut self) {
+        self.input_prompt = None;
+        self.input_buffer.clear();
+        self.pending_input_address = None;
+    }
+
+    pub fn input_submit(&mut self) {
+        let address = match self.pending_input_address.take() {
+            Some(address) => address,
+            None => return,
+        };
+        let push_history = self.pending_input_push_history;
+        let mut url = match Url::parse(&address) {
+            Ok(url) => url,
+            Err(_) => return,
+        };
+        url.set_query(Some(&self.input_buffer));
+
+        self.input_prompt = None;
+        self.input_buffer.clear();
+
+        self.dispatch(Action::PageRequest {
+            link: url.as_str().to_string(),
+            push_history,
+        });
+    }
+
+    pub fn start_search(&mut self) {
+        self.searching = true;
+        self.search_query.clear();
+        self.matches.clear();
+        self.current_match = None;
+    }
+
+    pub fn search_push_char(&mut self, c: char) {
+        if self.searching {
+            self.search_query.push(c);
+            self.recompute_matches();
+        }
+    }
+
+    pub fn search_backspace(&mut self) {
+        if self.searching {
+            self.search_query.pop();
+            self.recompute_matches();
+        }
+    }
+
+    pub fn search_cancel(&mut self) {
+        self.searching = false;
+        self.search_query.clear();
+        self.matches.clear();
+        self.current_match = None;
+    }
+
+    pub fn search_confirm(&mut self) {
+        self.searching = false;
+        self.jump_to_current_match();
+    }
+
+    pub fn next_match(&mut self) {
+        if self.matches.is_empty() {
+            return;
+        }
+        self.current_match = Some(match self.current_match {
+            Some(i) if i + 1 < self.matches.len() => i + 1,
+            _ => 0,
+        });
+        self.jump_to_current_match();
+    }
+
+    pub fn previous_match(&mut self) {
+        if self.matches.is_empty() {
+            return;
+        }
+        self.current_match = Some(match self.current_match {
+            Some(0) | None => self.matches.len() - 1,
+            Some(i) => i - 1,
+        });
+        self.jump_to_current_match();
+    }
+
+    fn jump_to_current_match(&mut self) {
+        if let Some(i) = self.current_match {
+            let (line_index, _, _) = self.matches[i];
+            let max_scroll = self.page.lines.len().saturating_sub(1) as u16;
+            self.scroll = (line_index as u16).min(max_scroll);
+        }
+    }
+
+    /// Recomputes `matches` against the query typed so far. Matching is
+    /// case-insensitive and done char-by-char directly against `text`, so
+    /// offsets always refer to valid char boundaries in the original
+    /// string — unlike scanning a `to_lowercase()` copy, whose byte length
+    /// can differ from the original's (e.g. `İ` is 2 bytes but lowercases
+    /// to a 3-byte `i̇`), which would desync the offsets from `text`.
+    fn recompute_matches(&mut self) {
+        self.matches.clear();
+
+        if !self.search_query.is_empty() {
+            for (index, line) in self.page.lines.iter().enumerate() {
+                // Only `Text` lines are rendered with `highlight_matches`, so
+                // restrict matches to them — headings/list items/links build
+                // their own decorated strings that the stored byte offsets
+                // don't line up with.
+                if !matches!(line.kind(), LineKind::Text) {
+                    continue;
+                }
+                if let Some(text) = line.text() {
+                    for (col_start, col_len) in find_matches(text, &self.search_query) {
+                        self.matches.push((index, col_start, col_len));
+                    }
+                }
+            }
+        }
+
+        self.current_match = if self.matches.is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+    }
+
+    #[inline]
+    pub fn awaiting_mark(&self) -> bool {
+        self.pending_mark.is_some()
+    }
+
+    /// Whether a modal text-entry or overlay mode (input prompt, in-page
+    /// search, mark set/jump, TOC) is capturing input, so that page
+    /// interactions like mouse clicks/scrolling should be suppressed.
+    #[inline]
+    pub fn modal_active(&self) -> bool {
+        self.input_prompt.is_some() || self.searching || self.awaiting_mark() || self.toc_open
+    }
+
+    pub fn start_set_mark(&mut self) {
+        self.pending_mark = Some(PendingMark::Set);
+    }
+
+    pub fn start_jump_mark(&mut self) {
+        self.pending_mark = Some(PendingMark::Jump);
+    }
+
+    pub fn cancel_pending_mark(&mut self) {
+        self.pending_mark = None;
+    }
+
+    pub fn handle_mark_key(&mut self, c: char) {
+        match self.pending_mark.take() {
+            Some(PendingMark::Set) => {
+                self.marks.insert(c, (self.address.clone(), self.scroll));
+            }
+            Some(PendingMark::Jump) => {
+                if let Some((address, scroll)) = self.marks.get(&c).cloned() {
+                    self.pending_scroll = Some(scroll);
+                    self.dispatch(Action::PageRequest {
+                        link: address,
+                        push_history: true,
+                    });
+                }
+            }
+            None => {}
+        }
+    }
+
+    pub fn add_bookmark(&mut self) {
+        if !self.bookmarks.contains(&self.address) {
+            self.bookmarks.push(self.address.clone());
+            save_bookmarks(&self.bookmarks);
+        }
+    }
+
+    /// Renders the bookmark list as a page, reusing the normal link
+    /// selection machinery so `j`/`k`/Enter work on it unchanged.
+    pub fn show_bookmarks(&mut self) {
+        let mut body = String::from("# Bookmarks\n\n");
+        for bookmark in &self.bookmarks {
+            body.push_str("=> ");
+            body.push_str(bookmark);
+            body.push('\n');
+        }
+        let lines = Lines::from(body).filter_map(|line| line.ok()).collect();
+        self.apply_page(BOOKMARKS_ADDRESS.to_string(), lines, false);
+    }
+
+    pub fn toggle_toc(&mut self) {
+        self.toc_open = !self.toc_open;
+        self.toc_selected = if self.toc_open && !self.page.heading_indices.is_empty() {
+            Some(0)
+        } else {
+            None
+        };
+    }
+
+    pub fn toc_next(&mut self) {
+        if self.page.heading_indices.is_empty() {
+            return;
+        }
+        self.toc_selected = Some(match self.toc_selected {
+            Some(i) if i + 1 < self.page.heading_indices.len() => i + 1,
+            _ => 0,
+        });
+    }
+
+    pub fn toc_previous(&mut self) {
+        if self.page.heading_indices.is_empty() {
+            return;
+        }
+        self.toc_selected = Some(match self.toc_selected {
+            Some(0) | None => self.page.heading_indices.len() - 1,
+            Some(i) => i - 1,
+        });
+    }
+
+    pub fn toc_select(&mut self) {
+        if let Some(i) = self.toc_selected {
+            self.scroll = self.page.heading_indices[i] as u16;
+        }
+        self.toc_open = false;
+        self.toc_selected = None;
+    }
+
+    pub fn next_heading(&mut self) {
+        if let Some(&line_index) = self
+            .page
+            .heading_indices
+            .iter()
+            .find(|&&i| i as u16 > self.scroll)
+        {
+            self.scroll = line_index as u16;
+        }
+    }
+
+    pub fn previous_heading(&mut self) {
+        if let Some(&line_index) = self
+            .page
+            .heading_indices
+            .iter()
+            .rev()
+            .find(|&&i| (i as u16) < self.scroll)
+        {
+            self.scroll = line_index as u16;
+        }
+    }
+
     fn drain_events(&mut self) -> Result<()> {
         loop {
             match self.in_rx.try_recv() {
@@ -263,25 +636,72 @@ impl App {
                 address,
                 lines,
                 push_history,
+                status,
+                meta,
             } => {
-                self.page.lines = lines;
-                self.page.link_indices = vec![];
-                for (i, line) in self.page.lines.iter().enumerate() {
-                    if let LineKind::Link = line.kind() {
-                        self.page
-                            .link_numbers
-                            .insert(i, self.page.link_indices.len());
-                        self.page.link_indices.push(i);
-                    }
-                }
-                self.page.highlighted_link = None;
-                self.scroll = 0;
-                self.address = address;
-                if push_history {
-                    self.history.push(self.address.clone());
+                self.last_status = Some(status);
+                self.last_meta = Some(meta);
+                self.apply_page(address, lines, push_history);
+            }
+            NetworkEvent::InputRequired {
+                address,
+                prompt,
+                push_history,
+            } => {
+                self.input_prompt = Some(prompt);
+                self.input_buffer.clear();
+                self.pending_input_address = Some(address);
+                self.pending_input_push_history = push_history;
+            }
+            NetworkEvent::LoadError {
+                address,
+                status,
+                meta,
+            } => {
+                self.last_status = Some(status);
+                self.last_meta = Some(meta.clone());
+                let body = format!("# Error\n\nstatus {}: {}\n", status, meta);
+                let lines = Lines::from(body).filter_map(|line| line.ok()).collect();
+                self.apply_page(address, lines, false);
+            }
+            NetworkEvent::ClientCertRequired { address, meta } => {
+                self.last_status = Some(60);
+                self.last_meta = Some(meta.clone());
+                let body = format!(
+                    "# Client certificate required\n\n{}\n\nComet doesn't support client certificates yet.\n",
+                    meta
+                );
+                let lines = Lines::from(body).filter_map(|line| line.ok()).collect();
+                self.apply_page(address, lines, false);
+            }
+        }
+    }
+
+    fn apply_page(&mut self, address: String, lines: Vec<Line>, push_history: bool) {
+        self.page.lines = lines;
+        self.page.link_indices = vec![];
+        self.page.link_numbers.clear();
+        self.page.heading_indices = vec![];
+        for (i, line) in self.page.lines.iter().enumerate() {
+            match line.kind() {
+                LineKind::Link => {
+                    self.page
+                        .link_numbers
+                        .insert(i, self.page.link_indices.len());
+                    self.page.link_indices.push(i);
                 }
+                LineKind::Heading => self.page.heading_indices.push(i),
+                _ => {}
             }
         }
+        self.page.highlighted_link = None;
+        self.toc_open = false;
+        self.toc_selected = None;
+        self.scroll = self.pending_scroll.take().unwrap_or(0);
+        self.address = address;
+        if push_history {
+            self.history.push(self.address.clone());
+        }
     }
 
     pub fn dispatch(&mut self, action: Action) {
@@ -1,10 +1,11 @@
-use crossterm::event::{Event as CrosstermEvent, KeyEvent};
+use crossterm::event::{Event as CrosstermEvent, KeyEvent, MouseEvent};
 use std::sync::mpsc::{channel, Receiver, RecvError, Sender};
 use std::thread;
 use std::time::Duration;
 
 pub enum Event {
     Input(KeyEvent),
+    Mouse(MouseEvent),
     Tick,
 }
 
@@ -20,8 +21,10 @@ impl Events {
 
         thread::spawn(move || loop {
             if crossterm::event::poll(timeout).unwrap() {
-                if let CrosstermEvent::Key(event) = crossterm::event::read().unwrap() {
-                    tx2.send(Event::Input(event)).unwrap();
+                match crossterm::event::read().unwrap() {
+                    CrosstermEvent::Key(event) => tx2.send(Event::Input(event)).unwrap(),
+                    CrosstermEvent::Mouse(event) => tx2.send(Event::Mouse(event)).unwrap(),
+                    _ => {}
                 }
             }
             tx2.send(Event::Tick).unwrap();
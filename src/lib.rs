@@ -9,7 +9,9 @@ use crate::{
 };
 use anyhow::Result;
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture, KeyCode, KeyModifiers},
+    event::{
+        DisableMouseCapture, EnableMouseCapture, KeyCode, KeyModifiers, MouseButton, MouseEventKind,
+    },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -21,7 +23,7 @@ use tui::{
     widgets::{Block, Borders, Paragraph},
     Frame, Terminal,
 };
-use ui::render_page;
+use ui::{render_page, render_toc};
 
 pub fn start_ui() -> Result<()> {
     // setup terminal
@@ -44,6 +46,48 @@ pub fn start_ui() -> Result<()> {
         terminal.draw(|f| render_app(f, &mut app))?;
         match events.recv()? {
             Event::Input(event) => {
+                if app.input_prompt.is_some() {
+                    match event.code {
+                        KeyCode::Enter => app.input_submit(),
+                        KeyCode::Esc => app.input_cancel(),
+                        KeyCode::Backspace => app.input_backspace(),
+                        KeyCode::Char(c) => app.input_push_char(c),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                if app.searching {
+                    match event.code {
+                        KeyCode::Enter => app.search_confirm(),
+                        KeyCode::Esc => app.search_cancel(),
+                        KeyCode::Backspace => app.search_backspace(),
+                        KeyCode::Char(c) => app.search_push_char(c),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                if app.awaiting_mark() {
+                    match event.code {
+                        KeyCode::Char(c) => app.handle_mark_key(c),
+                        KeyCode::Esc => app.cancel_pending_mark(),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                if app.toc_open {
+                    match event.code {
+                        KeyCode::Enter => app.toc_select(),
+                        KeyCode::Esc => app.toggle_toc(),
+                        KeyCode::Char('j') | KeyCode::Down => app.toc_next(),
+                        KeyCode::Char('k') | KeyCode::Up => app.toc_previous(),
+                        _ => {}
+                    }
+                    continue;
+                }
+
                 if event.code == KeyCode::Char('q') {
                     break;
                 }
@@ -76,10 +120,53 @@ pub fn start_ui() -> Result<()> {
                 if event.code == KeyCode::Char('k') {
                     app.previous_link();
                 }
+                if event.code == KeyCode::Char('/') {
+                    app.start_search();
+                }
+                if event.code == KeyCode::Char('n') {
+                    app.next_match();
+                }
+                if event.code == KeyCode::Char('N') {
+                    app.previous_match();
+                }
+                if event.code == KeyCode::Char('m') {
+                    app.start_set_mark();
+                }
+                if event.code == KeyCode::Char('`') || event.code == KeyCode::Char('\'') {
+                    app.start_jump_mark();
+                }
+                if event.code == KeyCode::Char('a') {
+                    app.add_bookmark();
+                }
+                if event.code == KeyCode::Char('B') {
+                    app.show_bookmarks();
+                }
+                if event.code == KeyCode::Char('t') {
+                    app.toggle_toc();
+                }
+                if event.code == KeyCode::Char(']') {
+                    app.next_heading();
+                }
+                if event.code == KeyCode::Char('[') {
+                    app.previous_heading();
+                }
                 if event.code == KeyCode::Esc {
                     app.clear_highlighted();
                 }
             }
+            Event::Mouse(event) => {
+                if app.modal_active() {
+                    continue;
+                }
+                match event.kind {
+                    MouseEventKind::ScrollDown => app.scroll_down(),
+                    MouseEventKind::ScrollUp => app.scroll_up(),
+                    MouseEventKind::Down(MouseButton::Left) => {
+                        app.follow_link_at(event.column, event.row);
+                    }
+                    _ => {}
+                }
+            }
             Event::Tick => {
                 app.tick()?;
             }
@@ -102,12 +189,58 @@ fn render_app<B: Backend>(f: &mut Frame<B>, app: &mut App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
-        .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
         .split(f.size());
-    let block = Block::default().title("Search").borders(Borders::ALL);
-    let paragraph = Paragraph::new("gemini://gemini.circumlunar.space/").block(block);
+    let (title, content) = if let Some(prompt) = &app.input_prompt {
+        (prompt.as_str(), app.input_buffer.as_str())
+    } else if app.searching {
+        ("Find", app.search_query.as_str())
+    } else {
+        ("Search", "gemini://gemini.circumlunar.space/")
+    };
+    let block = Block::default().title(title).borders(Borders::ALL);
+    let paragraph = Paragraph::new(content).block(block);
     f.render_widget(paragraph, chunks[0]);
 
     app.height = chunks[1].height.saturating_sub(2);
+    app.page_area = chunks[1];
     render_page(f, app, chunks[1]);
+    if app.toc_open {
+        render_toc(f, app, chunks[1]);
+    }
+
+    f.render_widget(Paragraph::new(footer_text(app)), chunks[2]);
+}
+
+/// One-line status summary: reading progress, link count, the last
+/// response's status/meta, and whether history can go back/forward.
+fn footer_text(app: &App) -> String {
+    let total_lines = app.page.lines.len() as u16;
+    let progress = if total_lines <= app.height {
+        100
+    } else {
+        let max_scroll = total_lines - app.height;
+        (app.scroll.min(max_scroll) as u32 * 100 / max_scroll as u32) as u16
+    };
+
+    let status = match (&app.last_status, &app.last_meta) {
+        (Some(status), Some(meta)) => format!("{} {}", status, meta),
+        _ => "-".to_string(),
+    };
+
+    format!(
+        "{}% | {} links | {} | prev: {} next: {}",
+        progress,
+        app.page.link_indices.len(),
+        status,
+        if app.history.has_prev() { "yes" } else { "no" },
+        if app.history.has_next() { "yes" } else { "no" },
+    )
 }
@@ -0,0 +1,141 @@
+use anyhow::anyhow;
+use minigem::{Line, Lines, Request};
+
+use std::sync::mpsc::Sender;
+use std::thread;
+
+use url::{ParseError, Url};
+
+/// Gemini response status codes are two digits; the leading digit is the
+/// category and is all that `fetch` branches on.
+const STATUS_INPUT: u8 = 1;
+const STATUS_SUCCESS: u8 = 2;
+const STATUS_REDIRECT: u8 = 3;
+const STATUS_CLIENT_CERT: u8 = 6;
+
+/// Redirects are followed automatically, but only this many times, so a
+/// misconfigured server can't send us into an infinite loop.
+const MAX_REDIRECTS: u8 = 5;
+
+pub enum NetworkEvent {
+    PageLoaded {
+        address: String,
+        lines: Vec<Line>,
+        push_history: bool,
+        status: u8,
+        meta: String,
+    },
+    InputRequired {
+        address: String,
+        prompt: String,
+        push_history: bool,
+    },
+    LoadError {
+        address: String,
+        status: u8,
+        meta: String,
+    },
+    ClientCertRequired {
+        address: String,
+        meta: String,
+    },
+}
+
+pub fn load_page(url: String, push_history: bool, cb: Sender<NetworkEvent>) {
+    thread::spawn(move || {
+        let event = fetch(url, push_history);
+        cb.send(event).unwrap();
+    });
+}
+
+fn fetch(url: String, push_history: bool) -> NetworkEvent {
+    let mut address = url;
+
+    // One initial request plus up to `MAX_REDIRECTS` followed redirects.
+    for _ in 0..=MAX_REDIRECTS {
+        let res = match Request::new(&address).send() {
+            Ok(res) => res,
+            Err(err) => {
+                return NetworkEvent::LoadError {
+                    address,
+                    status: 0,
+                    meta: err.to_string(),
+                }
+            }
+        };
+
+        match res.status / 10 {
+            STATUS_INPUT => {
+                return NetworkEvent::InputRequired {
+                    address,
+                    prompt: res.meta,
+                    push_history,
+                }
+            }
+            STATUS_SUCCESS => {
+                return match read_lines(res.body) {
+                    Ok(lines) => NetworkEvent::PageLoaded {
+                        address,
+                        lines,
+                        push_history,
+                        status: res.status,
+                        meta: res.meta,
+                    },
+                    Err(err) => NetworkEvent::LoadError {
+                        address,
+                        status: res.status,
+                        meta: err.to_string(),
+                    },
+                }
+            }
+            STATUS_REDIRECT => match resolve_redirect(&address, &res.meta) {
+                Ok(next) => address = next,
+                Err(err) => {
+                    return NetworkEvent::LoadError {
+                        address,
+                        status: res.status,
+                        meta: err.to_string(),
+                    }
+                }
+            },
+            STATUS_CLIENT_CERT => {
+                return NetworkEvent::ClientCertRequired {
+                    address,
+                    meta: res.meta,
+                }
+            }
+            _ => {
+                return NetworkEvent::LoadError {
+                    address,
+                    status: res.status,
+                    meta: res.meta,
+                }
+            }
+        }
+    }
+
+    NetworkEvent::LoadError {
+        address,
+        status: STATUS_REDIRECT * 10,
+        meta: "too many redirects".to_string(),
+    }
+}
+
+fn resolve_redirect(address: &str, target: &str) -> anyhow::Result<String> {
+    match Url::parse(target) {
+        Ok(url) => Ok(url.as_str().to_string()),
+        Err(ParseError::RelativeUrlWithoutBase) => {
+            let base = Url::parse(address)?;
+            Ok(base.join(target)?.as_str().to_string())
+        }
+        Err(err) => Err(anyhow!(err)),
+    }
+}
+
+fn read_lines(body: String) -> anyhow::Result<Vec<Line>> {
+    let mut lines = Vec::new();
+    for line in Lines::from(body) {
+        lines.push(line.map_err(|err| anyhow!("{:?}", err))?);
+    }
+    Ok(lines)
+}
@@ -1,22 +1,31 @@
 use crate::app::App;
 use minigem::{Line, LineKind};
+use std::sync::OnceLock;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::SyntaxSet;
 use tui::{
     backend::Backend,
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
     text::{Span, Spans},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
     Frame,
 };
 
+static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+static THEME_SET: OnceLock<ThemeSet> = OnceLock::new();
+
+fn syntax_set() -> &'static SyntaxSet {
+    SYNTAX_SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn theme_set() -> &'static ThemeSet {
+    THEME_SET.get_or_init(ThemeSet::load_defaults)
+}
+
 pub fn render_page<B: Backend>(f: &mut Frame<B>, app: &App, layout_chunk: Rect) {
-    let text: Vec<Spans> = app
-        .page
-        .lines
-        .iter()
-        .enumerate()
-        .map(|(index, line)| span_from_line(line, app, index))
-        .collect();
+    let text = render_lines(app);
     let block = Block::default()
         .title(&app.address[..])
         .borders(Borders::ALL);
@@ -24,9 +33,68 @@ pub fn render_page<B: Backend>(f: &mut Frame<B>, app: &App, layout_chunk: Rect)
     f.render_widget(paragraph, layout_chunk)
 }
 
+/// Renders every line of the page, tracking whether we're inside a
+/// preformatted block so content lines can be syntax-highlighted against
+/// the language named by the block's opening toggle line.
+fn render_lines(app: &App) -> Vec<Spans> {
+    let mut out = Vec::with_capacity(app.page.lines.len());
+    let mut highlighter: Option<HighlightLines> = None;
+    let mut in_preformat = false;
+
+    for (index, line) in app.page.lines.iter().enumerate() {
+        match line.kind() {
+            LineKind::PreformatToggle => {
+                in_preformat = !in_preformat;
+                highlighter = if in_preformat {
+                    line.text()
+                        .filter(|alt_text| !alt_text.is_empty())
+                        .and_then(|alt_text| syntax_set().find_syntax_by_token(alt_text))
+                        .map(|syntax| {
+                            HighlightLines::new(syntax, &theme_set().themes["base16-ocean.dark"])
+                        })
+                } else {
+                    None
+                };
+                out.push(Spans::from(""));
+            }
+            LineKind::Preformatted => {
+                out.push(preformatted_line(
+                    line.text().unwrap_or(""),
+                    &mut highlighter,
+                ));
+            }
+            _ => out.push(span_from_line(line, app, index)),
+        }
+    }
+
+    out
+}
+
+fn preformatted_line<'a>(text: &'a str, highlighter: &mut Option<HighlightLines>) -> Spans<'a> {
+    match highlighter {
+        Some(highlighter) => match highlighter.highlight_line(text, syntax_set()) {
+            Ok(ranges) => Spans::from(
+                ranges
+                    .into_iter()
+                    .map(|(style, piece)| {
+                        let color = Color::Rgb(
+                            style.foreground.r,
+                            style.foreground.g,
+                            style.foreground.b,
+                        );
+                        Span::styled(piece, Style::default().fg(color))
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            Err(_) => Spans::from(Span::styled(text, Style::default().fg(Color::DarkGray))),
+        },
+        None => Spans::from(Span::styled(text, Style::default().fg(Color::DarkGray))),
+    }
+}
+
 fn span_from_line<'a>(line: &'a Line, app: &'a App, index: usize) -> Spans<'a> {
     match line.kind() {
-        LineKind::Text => Spans::from(line.text().unwrap()),
+        LineKind::Text => Spans::from(highlight_matches(line.text().unwrap(), app, index)),
         LineKind::Heading => {
             let s = format!(
                 "{} {}",
@@ -56,3 +124,105 @@ fn span_from_line<'a>(line: &'a Line, app: &'a App, index: usize) -> Spans<'a> {
         _ => Spans::from("unsupported line type"),
     }
 }
+
+/// Splits `text` around any search matches that fall on `line_index`,
+/// rendering them with the same highlight style links use, plus a brighter
+/// background for the active match.
+fn highlight_matches<'a>(text: &'a str, app: &'a App, line_index: usize) -> Vec<Span<'a>> {
+    let line_matches: Vec<(usize, &(usize, usize, usize))> = app
+        .matches
+        .iter()
+        .enumerate()
+        .filter(|(_, (index, _, _))| *index == line_index)
+        .collect();
+
+    if line_matches.is_empty() {
+        return vec![Span::raw(text)];
+    }
+
+    let mut spans = Vec::new();
+    let mut cursor = 0;
+
+    for (match_index, (_, col_start, col_len)) in line_matches {
+        let end = col_start + col_len;
+        // Overlapping matches only highlight the part not already emitted.
+        if end <= cursor {
+            continue;
+        }
+        let start = (*col_start).max(cursor);
+        if start > cursor {
+            spans.push(Span::raw(&text[cursor..start]));
+        }
+
+        let style = if app.current_match == Some(match_index) {
+            Style::default().bg(Color::LightYellow)
+        } else {
+            Style::default().bg(Color::Cyan)
+        };
+        spans.push(Span::styled(&text[start..end], style));
+        cursor = end;
+    }
+
+    if cursor < text.len() {
+        spans.push(Span::raw(&text[cursor..]));
+    }
+
+    spans
+}
+
+/// Draws the table-of-contents overlay: headings indented by level, with
+/// the selected entry highlighted the same way link selection is.
+pub fn render_toc<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let popup_area = centered_rect(60, 60, area);
+
+    let items: Vec<ListItem> = app
+        .page
+        .heading_indices
+        .iter()
+        .enumerate()
+        .map(|(toc_index, &line_index)| {
+            let line = &app.page.lines[line_index];
+            let indent = "  ".repeat(line.level().unwrap_or(1).saturating_sub(1));
+            let text = format!("{}{}", indent, line.text().unwrap_or(""));
+            let style = match app.toc_selected {
+                Some(selected) if selected == toc_index => Style::default().bg(Color::Cyan),
+                _ => Style::default(),
+            };
+            ListItem::new(text).style(style)
+        })
+        .collect();
+
+    let block = Block::default()
+        .title("Table of Contents")
+        .borders(Borders::ALL);
+    let list = List::new(items).block(block);
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(list, popup_area);
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ]
+            .as_ref(),
+        )
+        .split(vertical[1])[1]
+}